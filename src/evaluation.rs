@@ -2,8 +2,10 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
-use super::{Accuracy, Case};
+use super::{Accuracy, Case, ClassMetrics, ClassificationReport, InductionError, ProbabilisticCase};
+use itertools::Itertools;
 use ndarray::{ArrayView, Ix1, Zip};
+use std::hash::Hash;
 
 /// Apply a set of cases to an attribute value to get a prediction.
 ///
@@ -30,14 +32,83 @@ pub fn interpret<'c, A: PartialEq, C>(
         .map(|case| &case.predicted_class)
 }
 
+/// Like `interpret`, but falls back to a `default` class instead of returning `None` when the
+/// attribute value wasn't seen while the cases were generated.
+///
+/// This is intended for use with the 0R baseline (see `discover_zero_r`): rather than treating an
+/// unseen attribute value as an automatic miss, callers can opt in to predicting the dataset's
+/// most frequent class instead.
+///
+/// # Examples
+///
+/// ```
+/// use oner_induction::{Case, interpret_or_default};
+///
+/// let cases = vec![
+///     Case { attribute_value: "summer", predicted_class: "hot" },
+///     Case { attribute_value: "winter", predicted_class: "cold" },
+/// ];
+///
+/// assert_eq!(&"hot", interpret_or_default(&cases, &"summer", &"cold"));
+/// assert_eq!(&"cold", interpret_or_default(&cases, &"spring", &"cold"));
+/// ```
+pub fn interpret_or_default<'c, A: PartialEq, C>(
+    cases: &'c [Case<A, C>],
+    attribute_value: &A,
+    default: &'c C,
+) -> &'c C {
+    interpret(cases, attribute_value).unwrap_or(default)
+}
+
+/// Like `interpret`, but returns the full class-probability distribution for the matched
+/// attribute value (see `discover_proba_for_attribute`) instead of only the most likely class.
+///
+/// # Examples
+///
+/// ```
+/// use oner_induction::{ProbabilisticCase, interpret_proba};
+///
+/// let cases = vec![
+///     ProbabilisticCase { attribute_value: "summer", class_probs: vec![("hot", 0.8), ("cold", 0.2)] },
+/// ];
+///
+/// assert_eq!(Some(&[("hot", 0.8), ("cold", 0.2)][..]), interpret_proba(&cases, &"summer"));
+/// assert_eq!(None, interpret_proba(&cases, &"winter"));
+/// ```
+pub fn interpret_proba<'c, A: PartialEq, C>(
+    cases: &'c [ProbabilisticCase<A, C>],
+    attribute_value: &A,
+) -> Option<&'c [(C, f64)]> {
+    cases
+        .iter()
+        .find(|case| &case.attribute_value == attribute_value)
+        .map(|case| case.class_probs.as_slice())
+}
+
 /// Evaluate cases (a.k.a., a rule) against a data set, to get a performance accuracy.
 ///
 /// Accuracy is defined as the number of correct predictions over the number of rows.
+///
+/// # Errors
+///
+/// `InductionError::MismatchedLengths` if `attribute_values` and `classes` don't have the same
+/// length, and `InductionError::EmptyDataset` if they're empty.
 pub fn evaluate<A: PartialEq, C: PartialEq>(
     cases: &[Case<A, C>],
     attribute_values: &ArrayView<A, Ix1>,
     classes: &ArrayView<C, Ix1>,
-) -> Accuracy {
+) -> Result<Accuracy, InductionError> {
+    if attribute_values.len() != classes.len() {
+        return Err(InductionError::MismatchedLengths {
+            attribute_rows: attribute_values.len(),
+            class_rows: classes.len(),
+        });
+    }
+
+    if classes.is_empty() {
+        return Err(InductionError::EmptyDataset);
+    }
+
     let mut right_wrong: Vec<Option<bool>> = Vec::new();
 
     Zip::from(attribute_values).and(classes).apply(|attribute_value, class| {
@@ -48,11 +119,162 @@ pub fn evaluate<A: PartialEq, C: PartialEq>(
     });
 
     let num_examples = classes.len();
+    let num_correct = right_wrong.into_iter().filter(|&o| o == Some(true)).count();
+
+    Ok(Accuracy(num_correct as f64 / num_examples as f64))
+}
+
+/// Evaluate cases against a data set, like `evaluate`, but return a full `ClassificationReport`:
+/// a confusion matrix and per-class precision/recall/F1, alongside the overall accuracy.
+///
+/// This is more informative than `evaluate`'s single accuracy fraction when classes are
+/// imbalanced, since a rule can have high accuracy while performing badly on a minority class.
+///
+/// # Errors
+///
+/// `InductionError::MismatchedLengths` if `attribute_values` and `classes` don't have the same
+/// length, and `InductionError::EmptyDataset` if they're empty.
+///
+/// # Examples
+///
+/// ```
+/// use ndarray::prelude::*;
+/// use oner_induction::{Case, evaluate_detailed};
+///
+/// let cases = vec![
+///     Case { attribute_value: "summer", predicted_class: "hot" },
+///     Case { attribute_value: "winter", predicted_class: "cold" },
+/// ];
+///
+/// let attribute_values = array!["summer", "summer", "winter"];
+/// let classes = array!["hot", "cold", "cold"];
+///
+/// let report = evaluate_detailed(&cases, &attribute_values.view(), &classes.view()).unwrap();
+///
+/// assert_eq!(report.accuracy.0, 2.0 / 3.0);
+/// assert_eq!(report.unmatched, 0);
+/// ```
+pub fn evaluate_detailed<A: PartialEq, C: Eq + Hash + Clone>(
+    cases: &[Case<A, C>],
+    attribute_values: &ArrayView<A, Ix1>,
+    classes: &ArrayView<C, Ix1>,
+) -> Result<ClassificationReport<C>, InductionError> {
+    if attribute_values.len() != classes.len() {
+        return Err(InductionError::MismatchedLengths {
+            attribute_rows: attribute_values.len(),
+            class_rows: classes.len(),
+        });
+    }
+
+    if classes.is_empty() {
+        return Err(InductionError::EmptyDataset);
+    }
+
+    // The distinct classes, in order of first appearance, index both axes of the confusion
+    // matrix. This must cover every class a rule can predict as well as every class actually
+    // observed, since a rule trained elsewhere may predict a class absent from this test split.
+    let distinct_classes: Vec<C> = classes
+        .iter()
+        .chain(cases.iter().map(|case| &case.predicted_class))
+        .unique()
+        .cloned()
+        .collect();
+    let num_classes = distinct_classes.len();
+
+    let mut confusion_matrix = vec![vec![0usize; num_classes]; num_classes];
+    // Per-class actual counts, including rows left unmatched by `interpret`, so that recall's
+    // denominator agrees with `accuracy` about what counts as "this class's examples".
+    let mut actual_totals = vec![0usize; num_classes];
+    let mut unmatched = 0usize;
+    let mut num_correct = 0usize;
+
+    Zip::from(attribute_values).and(classes).apply(|attribute_value, actual| {
+        let actual_index = distinct_classes.iter().position(|c| c == actual).unwrap();
+        actual_totals[actual_index] += 1;
+
+        match interpret(cases, attribute_value) {
+            None => unmatched += 1,
+            Some(predicted) => {
+                let predicted_index = distinct_classes.iter().position(|c| c == predicted).unwrap();
+                confusion_matrix[predicted_index][actual_index] += 1;
+
+                if predicted == actual {
+                    num_correct += 1;
+                }
+            }
+        }
+    });
+
+    let accuracy = Accuracy(num_correct as f64 / classes.len() as f64);
+
+    let per_class = (0..num_classes)
+        .map(|i| {
+            let true_positive = confusion_matrix[i][i];
+            let predicted_count: usize = confusion_matrix[i].iter().sum();
+            let actual_count = actual_totals[i];
+
+            let precision = if predicted_count == 0 {
+                0.0
+            } else {
+                true_positive as f64 / predicted_count as f64
+            };
+            let recall =
+                if actual_count == 0 { 0.0 } else { true_positive as f64 / actual_count as f64 };
+            let f1 = if precision + recall == 0.0 {
+                0.0
+            } else {
+                2.0 * precision * recall / (precision + recall)
+            };
+
+            ClassMetrics { precision, recall, f1 }
+        })
+        .collect();
 
-    if num_examples == 0 {
-        Accuracy(0.0)
-    } else {
-        let num_correct = right_wrong.into_iter().filter(|&o| o == Some(true)).count();
-        Accuracy(num_correct as f64 / num_examples as f64)
+    Ok(ClassificationReport {
+        accuracy,
+        classes: distinct_classes,
+        confusion_matrix,
+        per_class,
+        unmatched,
+    })
+}
+
+/// Like `evaluate`, but uses `interpret_or_default` so that attribute values unseen during
+/// training count against `default` rather than always counting as a miss.
+///
+/// This lets callers fall back to the 0R baseline (`discover_zero_r`) on rows whose attribute
+/// value wasn't in the training data, instead of discarding them.
+///
+/// # Errors
+///
+/// `InductionError::MismatchedLengths` if `attribute_values` and `classes` don't have the same
+/// length, and `InductionError::EmptyDataset` if they're empty.
+pub fn evaluate_with_default<A: PartialEq, C: PartialEq>(
+    cases: &[Case<A, C>],
+    attribute_values: &ArrayView<A, Ix1>,
+    classes: &ArrayView<C, Ix1>,
+    default: &C,
+) -> Result<Accuracy, InductionError> {
+    if attribute_values.len() != classes.len() {
+        return Err(InductionError::MismatchedLengths {
+            attribute_rows: attribute_values.len(),
+            class_rows: classes.len(),
+        });
+    }
+
+    if classes.is_empty() {
+        return Err(InductionError::EmptyDataset);
     }
+
+    let mut right_wrong: Vec<bool> = Vec::new();
+
+    Zip::from(attribute_values).and(classes).apply(|attribute_value, class| {
+        let predicted = interpret_or_default(cases, attribute_value, default);
+        right_wrong.push(predicted == class);
+    });
+
+    let num_examples = classes.len();
+    let num_correct = right_wrong.into_iter().filter(|&b| b).count();
+
+    Ok(Accuracy(num_correct as f64 / num_examples as f64))
 }
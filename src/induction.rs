@@ -3,15 +3,42 @@
 // file, You can obtain one at https://mozilla.org/MPL/2.0/.
 
 use super::evaluation::evaluate;
-use super::{Case, Rule};
+use super::{Accuracy, Case, ConjunctiveRule, InductionError, ProbabilisticCase, Rule};
 use itertools::Itertools;
 use ndarray::{ArrayView, Ix1, Ix2, Zip};
 use rustc_hash::FxHasher;
 use std::collections::HashMap;
 use std::hash::{BuildHasherDefault, Hash};
 
+/// Parameters controlling Holte's (1993) small-bucket overfitting avoidance.
+///
+/// Without these, 1R trivially overfits high-cardinality attributes: every distinct value becomes
+/// its own perfect, single-example case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InductionParams {
+    /// The minimum number of training examples that must support a case's predicted class.
+    /// Attribute values whose winning class has fewer supporting examples than this are folded
+    /// into a shared low-support "default" bucket instead of becoming their own case.
+    pub small: usize,
+
+    /// Attributes with more distinct values than this are rejected outright: their rule's
+    /// accuracy is marked unusable so `discover`/`discover_with` won't pick them.
+    pub distinct_above: usize,
+}
+
+impl Default for InductionParams {
+    /// No small-bucket folding (`small: 1`) and no rejection of high-cardinality attributes
+    /// (`distinct_above: usize::MAX`) - i.e. the original, unguarded 1R behaviour.
+    fn default() -> Self {
+        InductionParams { small: 1, distinct_above: usize::MAX }
+    }
+}
+
 /// Find the one rule that fits a set of example data points.
 ///
+/// Equivalent to `discover_with(&InductionParams::default(), ...)`: no small-bucket folding,
+/// and no attribute is rejected for having too many distinct values.
+///
 /// # Arguments
 ///
 /// * `attributes` - rows containing attribute values as columns.
@@ -28,20 +55,291 @@ use std::hash::{BuildHasherDefault, Hash};
 ///
 /// A `Case` is a value for the attribute and the corresponding predicted class.
 ///
+/// # Errors
+///
+/// See `discover_with`.
+///
 pub fn discover<A, C>(
     attributes: &ArrayView<A, Ix2>,
     classes: &ArrayView<C, Ix1>,
-) -> Option<(usize, Rule<A, C>)>
+) -> Result<(usize, Rule<A, C>), InductionError>
+where
+    A: Eq + Hash + Clone + std::fmt::Debug,
+    C: Eq + Hash + Clone + std::fmt::Debug,
+{
+    discover_with(&InductionParams::default(), attributes, classes)
+}
+
+/// Like `discover`, but with explicit control over Holte's small-bucket overfitting avoidance.
+///
+/// # Arguments
+///
+/// * `params` - see `InductionParams`.
+/// * `attributes` - rows containing attribute values as columns.
+/// * `classes` - the true classification for each row.
+///
+/// # Errors
+///
+/// `InductionError::EmptyDataset` if there are no rows, `InductionError::MismatchedLengths` if
+/// `attributes` and `classes` don't have the same number of rows, `InductionError::NoAttributes`
+/// if there are no columns to learn from, and `InductionError::AllAttributesRejected` if every
+/// attribute was rejected by `params.distinct_above`.
+///
+pub fn discover_with<A, C>(
+    params: &InductionParams,
+    attributes: &ArrayView<A, Ix2>,
+    classes: &ArrayView<C, Ix1>,
+) -> Result<(usize, Rule<A, C>), InductionError>
 where
     A: Eq + Hash + Clone + std::fmt::Debug,
     C: Eq + Hash + Clone + std::fmt::Debug,
 {
-    let rules: Vec<Rule<A, C>> = generate_hypotheses(attributes, classes);
+    if attributes.nrows() != classes.len() {
+        return Err(InductionError::MismatchedLengths {
+            attribute_rows: attributes.nrows(),
+            class_rows: classes.len(),
+        });
+    }
+
+    if classes.is_empty() {
+        return Err(InductionError::EmptyDataset);
+    }
+
+    if attributes.ncols() == 0 {
+        return Err(InductionError::NoAttributes);
+    }
+
+    let rules: Vec<Rule<A, C>> = generate_hypotheses(params, attributes, classes);
 
     // Find the best rule (highest accuracy), and the column number it applies to:
-    rules.into_iter().enumerate().max_by(|(_i, a), (_j, b)| {
+    let best = rules.into_iter().enumerate().max_by(|(_i, a), (_j, b)| {
         a.accuracy.partial_cmp(&b.accuracy).unwrap_or(std::cmp::Ordering::Equal)
-    })
+    });
+
+    match best {
+        Some((_, Rule { accuracy: Accuracy(accuracy), .. })) if accuracy == f64::NEG_INFINITY => {
+            Err(InductionError::AllAttributesRejected)
+        }
+        Some(rule) => Ok(rule),
+        None => Err(InductionError::NoAttributes),
+    }
+}
+
+/// Find the 0R ("zero rule") baseline: the single most frequent class, and its training accuracy.
+///
+/// 0R ignores the attributes entirely and always predicts the most frequent class in `classes`.
+/// It's the simplest possible baseline, and a useful sanity check: a 1R rule that can't beat 0R
+/// isn't learning anything from its attribute.
+///
+/// # Arguments
+///
+/// * `classes` - the true classification for each row.
+///
+/// # Result
+///
+/// The most frequent class, and the accuracy of always predicting it. `None` if `classes` is empty.
+///
+pub fn discover_zero_r<C>(classes: &ArrayView<C, Ix1>) -> Option<(C, Accuracy)>
+where
+    C: Eq + Hash + Clone,
+{
+    let mut class_count = HashMap::with_hasher(BuildHasherDefault::<FxHasher>::default());
+    for class in classes.iter() {
+        *class_count.entry(class).or_insert(0) += 1;
+    }
+
+    let (most_frequent_class, count) = class_count.into_iter().max_by_key(|&(_, count)| count)?;
+
+    let accuracy = Accuracy(count as f64 / classes.len() as f64);
+
+    Some((most_frequent_class.to_owned(), accuracy))
+}
+
+/// Find an ordered list of conjunctive rules using the PRISM covering algorithm.
+///
+/// Unlike 1R, a PRISM rule may test several attributes at once, so it can express classes that no
+/// single attribute separates cleanly.
+///
+/// For each class in turn, PRISM repeatedly builds a rule by greedily adding the `(column,
+/// value)` term that best separates the still-uncovered examples: among all terms not already in
+/// the antecedent, it picks the one maximising `p / t`, where `t` is the number of examples
+/// (of any class) currently satisfying the antecedent plus the candidate term, and `p` is how
+/// many of those belong to the target class (ties go to the larger `p`). Terms are added until
+/// the rule is pure (`p == t`) or no attributes remain, the rule is emitted, and every example it
+/// covers is removed before the next rule for that class is built. This repeats until no examples
+/// of the class remain uncovered.
+///
+/// # Arguments
+///
+/// * `attributes` - rows containing attribute values as columns.
+/// * `classes` - the true classification for each row.
+///
+/// # Result
+///
+/// The ordered list of rules (tried in order; the first whose antecedent matches wins), and the
+/// accuracy of that rule list on the training data.
+///
+/// # References
+///
+/// Cendrowska, J. (1987) PRISM: An algorithm for inducing modular rules. _International Journal
+/// of Man-Machine Studies_ 27(4): 349-370.
+///
+pub fn discover_prism<A, C>(
+    attributes: &ArrayView<A, Ix2>,
+    classes: &ArrayView<C, Ix1>,
+) -> (Vec<ConjunctiveRule<A, C>>, Accuracy)
+where
+    A: Eq + Hash + Clone + std::fmt::Debug,
+    C: Eq + Hash + Clone + std::fmt::Debug,
+{
+    let nrows = classes.len();
+    let ncols = attributes.ncols();
+
+    let mut rules: Vec<ConjunctiveRule<A, C>> = Vec::new();
+
+    // Distinct classes, in order of first appearance, for determinism:
+    let distinct_classes: Vec<C> = classes.iter().unique().map(|c| c.to_owned()).collect();
+
+    for class in &distinct_classes {
+        // Examples not yet covered by a rule emitted for this class. Shared across rules (not
+        // reset per rule): each rule removes whatever it covered, right or wrong, so the next
+        // rule for this class always works with a strictly smaller set and the loop terminates.
+        let mut available: Vec<usize> = (0..nrows).collect();
+
+        while available.iter().any(|&i| &classes[i] == class) {
+            let mut antecedent: Vec<(usize, A)> = Vec::new();
+            // Examples (of any class) still satisfying the antecedent built so far:
+            let mut covered: Vec<usize> = available.clone();
+
+            loop {
+                // The best (column, value) term not already in the antecedent: (col, value, p, t)
+                let mut best: Option<(usize, A, usize, usize)> = None;
+
+                for col in 0..ncols {
+                    if antecedent.iter().any(|(c, _)| *c == col) {
+                        continue;
+                    }
+
+                    let column = attributes.column(col);
+                    let candidate_values: Vec<A> =
+                        covered.iter().map(|&i| column[i].clone()).unique().collect();
+
+                    for value in candidate_values {
+                        let t = covered.iter().filter(|&&i| column[i] == value).count();
+                        let p = covered
+                            .iter()
+                            .filter(|&&i| column[i] == value && &classes[i] == class)
+                            .count();
+
+                        let is_better = match &best {
+                            None => true,
+                            Some((_, _, best_p, best_t)) => {
+                                let ratio = p as f64 / t as f64;
+                                let best_ratio = *best_p as f64 / *best_t as f64;
+                                ratio > best_ratio || (ratio == best_ratio && p > *best_p)
+                            }
+                        };
+
+                        if is_better {
+                            best = Some((col, value, p, t));
+                        }
+                    }
+                }
+
+                match best {
+                    None => break, // no attributes left to add
+                    Some((col, value, p, t)) => {
+                        covered.retain(|&i| attributes[[i, col]] == value);
+                        antecedent.push((col, value));
+
+                        if p == t {
+                            break; // the rule is pure
+                        }
+                    }
+                }
+            }
+
+            rules.push(ConjunctiveRule { antecedent, predicted_class: class.to_owned() });
+
+            available.retain(|i| !covered.contains(i));
+        }
+    }
+
+    let accuracy = evaluate_prism(&rules, attributes, classes);
+
+    (rules, accuracy)
+}
+
+/// Evaluate an ordered list of PRISM rules: the first rule whose antecedent matches a row makes
+/// the prediction for that row; rows matched by no rule count as misses.
+fn evaluate_prism<A: PartialEq, C: PartialEq + Clone>(
+    rules: &[ConjunctiveRule<A, C>],
+    attributes: &ArrayView<A, Ix2>,
+    classes: &ArrayView<C, Ix1>,
+) -> Accuracy {
+    let nrows = classes.len();
+
+    if nrows == 0 {
+        return Accuracy(0.0);
+    }
+
+    let num_correct = (0..nrows)
+        .filter(|&i| {
+            rules
+                .iter()
+                .find(|rule| {
+                    rule.antecedent.iter().all(|(col, value)| attributes[[i, *col]] == *value)
+                })
+                .is_some_and(|rule| rule.predicted_class == classes[i])
+        })
+        .count();
+
+    Accuracy(num_correct as f64 / nrows as f64)
+}
+
+/// Generate a `ProbabilisticCase` for every distinct value of a single attribute, carrying the
+/// full class distribution observed for that value rather than just its majority class.
+///
+/// This exposes the same per-value `class_count` that `generate_rule_for_attribute` computes
+/// internally before collapsing it down to an argmax, so callers can threshold or rank
+/// predictions instead of only taking the top class.
+///
+/// # Arguments
+///
+/// * `attribute_values` - the value of each attribute for all examples being used.
+/// * `classes` - the true value for each class.
+///
+pub fn discover_proba_for_attribute<A, C>(
+    attribute_values: &ArrayView<A, Ix1>,
+    classes: &ArrayView<C, Ix1>,
+) -> Vec<ProbabilisticCase<A, C>>
+where
+    A: Eq + Hash + Clone,
+    C: Eq + Hash + Clone,
+{
+    let mut cases: Vec<ProbabilisticCase<A, C>> = Vec::new();
+
+    let unique_values = attribute_values.iter().unique();
+
+    for v in unique_values {
+        let mut class_count = HashMap::with_hasher(BuildHasherDefault::<FxHasher>::default());
+        let mut total = 0usize;
+        Zip::from(attribute_values).and(classes).apply(|attribute_value, class| {
+            if attribute_value == v {
+                *class_count.entry(class).or_insert(0) += 1;
+                total += 1;
+            }
+        });
+
+        let class_probs: Vec<(C, f64)> = class_count
+            .into_iter()
+            .map(|(class, count)| (class.to_owned(), count as f64 / total as f64))
+            .collect();
+
+        cases.push(ProbabilisticCase { attribute_value: v.to_owned(), class_probs });
+    }
+
+    cases
 }
 
 #[cfg(test)]
@@ -83,11 +381,112 @@ mod test {
             accuracy: Accuracy(0.7),
         };
 
-        assert_eq!(rule, Some((1, expected_rule)));
+        assert_eq!(rule, Ok((1, expected_rule)));
+    }
+
+    #[test]
+    fn test_discover_zero_r() {
+        let classes = array!["high", "high", "high", "high", "medium", "medium", "low"];
+
+        let zero_r = discover_zero_r(&classes.view());
+
+        assert_eq!(zero_r, Some(("high", Accuracy(4.0 / 7.0))));
+    }
+
+    #[test]
+    fn test_discover_zero_r_empty() {
+        let classes: Array1<&str> = array![];
+
+        assert_eq!(discover_zero_r(&classes.view()), None);
+    }
+
+    #[test]
+    fn test_small_bucket_folds_low_support_values() {
+        // A high-cardinality id-like column: every value is unique, so without folding
+        // each would become its own (trivially perfect) case.
+        let attributes = array![["a"], ["b"], ["c"], ["d"]];
+        let classes = array!["yes", "yes", "yes", "no"];
+
+        let params = InductionParams { small: 2, distinct_above: usize::MAX };
+
+        let rule = discover_with(&params, &attributes.view(), &classes.view());
+
+        // Every value has support 1, below `small`, so all four fold into one bucket
+        // predicting the overall majority class, "yes".
+        let expected_rule = Rule {
+            cases: vec![
+                Case { attribute_value: "a", predicted_class: "yes" },
+                Case { attribute_value: "b", predicted_class: "yes" },
+                Case { attribute_value: "c", predicted_class: "yes" },
+                Case { attribute_value: "d", predicted_class: "yes" },
+            ],
+            accuracy: Accuracy(0.75),
+        };
+
+        assert_eq!(rule, Ok((0, expected_rule)));
+    }
+
+    #[test]
+    fn test_discover_proba_for_attribute() {
+        let size = array!["small", "big", "big", "medium", "medium", "small", "medium", "small", "medium", "small"];
+        let classes = array![
+            "high", "high", "high", "medium", "medium", "medium", "medium", "low", "low", "low",
+        ];
+
+        let cases = discover_proba_for_attribute(&size.view(), &classes.view());
+
+        // "big" only ever co-occurs with "high", so its distribution is a single, deterministic entry.
+        let big_case =
+            cases.iter().find(|case| case.attribute_value == "big").expect("a case for \"big\"");
+        assert_eq!(big_case.class_probs, vec![("high", 1.0)]);
+    }
+
+    #[test]
+    fn test_discover_prism() {
+        let attributes = array![
+            // Data from: Christoph Molnar's "Interpretable Machine Learning",
+            // licensed under https://creativecommons.org/licenses/by-nc-sa/4.0/
+            // rental property attributes: location,size,pets
+            ["good", "small", "yes"],
+            ["good", "big", "no"],
+            ["good", "big", "no"],
+            ["bad", "medium", "no"],
+            ["good", "medium", "only cats"],
+            ["good", "small", "only cats"],
+            ["bad", "medium", "yes"],
+            ["bad", "small", "yes"],
+            ["bad", "medium", "yes"],
+            ["bad", "small", "no"],
+        ];
+
+        let classes = array![
+            "high", "high", "high", "medium", "medium", "medium", "medium", "low", "low", "low",
+        ];
+
+        let (rules, accuracy) = discover_prism(&attributes.view(), &classes.view());
+
+        // Rows 6 and 8 have identical attributes ("bad", "medium", "yes") but different classes
+        // ("medium" and "low"), so no conjunction of attribute tests can separate them; 9/10 is
+        // the best any rule set can do on this data.
+        assert_eq!(accuracy, Accuracy(0.9));
+        assert!(!rules.is_empty());
+    }
+
+    #[test]
+    fn test_distinct_above_rejects_high_cardinality_attribute() {
+        let attributes = array![["a"], ["b"], ["c"], ["d"]];
+        let classes = array!["yes", "yes", "yes", "no"];
+
+        let params = InductionParams { small: 1, distinct_above: 2 };
+
+        let rule = generate_rule_for_attribute(&params, &attributes.column(0), &classes.view());
+
+        assert_eq!(rule.accuracy, Accuracy(f64::NEG_INFINITY));
     }
 }
 
 fn generate_hypotheses<A: Eq + Hash + Clone, C: Eq + Hash + Clone>(
+    params: &InductionParams,
     attributes: &ArrayView<A, Ix2>,
     classes: &ArrayView<C, Ix1>,
 ) -> Vec<Rule<A, C>> {
@@ -95,7 +494,7 @@ fn generate_hypotheses<A: Eq + Hash + Clone, C: Eq + Hash + Clone>(
 
     // Generate a rule for each attribute:
     for col in attributes.gencolumns() {
-        let hypothesis = generate_rule_for_attribute(&col, classes);
+        let hypothesis = generate_rule_for_attribute(params, &col, classes);
         hs.push(hypothesis);
     }
 
@@ -107,10 +506,18 @@ fn generate_hypotheses<A: Eq + Hash + Clone, C: Eq + Hash + Clone>(
 /// The process works by finding the most frequent class for each distinct
 /// attribute value. The most frequent class is the prediction for that attribute value.
 ///
+/// Following Holte (1993), any attribute value whose winning class is supported by fewer than
+/// `params.small` examples is folded into a shared low-support bucket, predicting the majority
+/// class across all folded values, rather than being trusted as its own case. If the attribute
+/// has more than `params.distinct_above` distinct values, the resulting rule's accuracy is set to
+/// negative infinity so `discover`/`discover_with` will never pick it.
+///
 /// The result is a set of "cases" (one "IF ... THEN" condition for each distinct attribute value).
 ///
 /// # Arguments
 ///
+/// * `params` - see `InductionParams`.
+///
 /// * `attribute_values` - the value of each attribute for all examples being used.
 ///
 /// * `clases` - the true value for each class.
@@ -119,6 +526,7 @@ fn generate_hypotheses<A: Eq + Hash + Clone, C: Eq + Hash + Clone>(
 /// The arguments must be of the same length. For each attribute value, there's a corresponding class.
 ///
 fn generate_rule_for_attribute<A, C>(
+    params: &InductionParams,
     attribute_values: &ArrayView<A, Ix1>,
     classes: &ArrayView<C, Ix1>,
 ) -> Rule<A, C>
@@ -128,9 +536,14 @@ where
 {
     let mut cases: Vec<Case<A, C>> = Vec::new();
 
-    let unique_values = attribute_values.iter().unique();
+    // Examples whose attribute value didn't have enough support for its own case, to be folded
+    // into a single shared "default" bucket once every value has been considered:
+    let mut small_bucket = HashMap::with_hasher(BuildHasherDefault::<FxHasher>::default());
+    let mut small_values: Vec<A> = Vec::new();
 
-    for v in unique_values {
+    let unique_values: Vec<A> = attribute_values.iter().unique().map(|v| v.to_owned()).collect();
+
+    for v in &unique_values {
         // Count the number of times we see each class, using deterministic hasher for reproducabiltiy with tied results
         let mut class_count = HashMap::with_hasher(BuildHasherDefault::<FxHasher>::default());
         Zip::from(attribute_values).and(classes).apply(|attribute_value, class| {
@@ -140,15 +553,40 @@ where
         });
 
         // The most frequent class is the preidction for the attribute value, v
-        let maybe_most_frequent_class =
-            class_count.into_iter().max_by_key(|&(_, count)| count).map(|(class, _)| class);
+        let maybe_most_frequent =
+            class_count.iter().max_by_key(|(_, count)| **count).map(|(class, count)| (*class, *count));
+
+        if let Some((class, support)) = maybe_most_frequent {
+            if support < params.small {
+                for (class, count) in class_count {
+                    *small_bucket.entry(class.to_owned()).or_insert(0) += count;
+                }
+                small_values.push(v.to_owned());
+            } else {
+                cases.push(Case { attribute_value: v.to_owned(), predicted_class: class.to_owned() });
+            }
+        }
+    }
 
-        if let Some(class) = maybe_most_frequent_class {
-            cases.push(Case { attribute_value: v.to_owned(), predicted_class: class.to_owned() });
+    if !small_values.is_empty() {
+        let default_class =
+            small_bucket.into_iter().max_by_key(|(_, count)| *count).map(|(class, _)| class);
+
+        if let Some(default_class) = default_class {
+            for v in small_values {
+                cases.push(Case { attribute_value: v, predicted_class: default_class.clone() });
+            }
         }
     }
 
-    let accuracy = evaluate(&cases, attribute_values, classes);
+    let accuracy = evaluate(&cases, attribute_values, classes)
+        .expect("attribute_values and classes are already validated to match by discover_with");
+
+    let accuracy = if unique_values.len() > params.distinct_above {
+        Accuracy(f64::NEG_INFINITY)
+    } else {
+        accuracy
+    };
 
     Rule { cases, accuracy }
 }
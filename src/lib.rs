@@ -22,7 +22,7 @@
 //!
 //! ```
 //! use ndarray::prelude::*;
-//! use oner_induction::{Rule, Case, Accuracy, discover};
+//! use oner_induction::{Rule, Case, Accuracy, InductionError, discover};
 //!
 //! let examples = array![
 //!    ["sunny", "summer"],
@@ -39,7 +39,7 @@
 //! ];
 //!
 //! // Discover the best rule, and the column it applies to:
-//! let rule: Option<(usize, Rule<&str, &str>)> =
+//! let rule: Result<(usize, Rule<&str, &str>), InductionError> =
 //!   discover(&examples.view(), &classes.view());
 //!
 //! // Expected accuracy is 100%
@@ -52,7 +52,7 @@
 //! ];
 //!
 //! // Column 1 is the Season (winter or summer)
-//! assert_eq!(rule, Some( (1, Rule { cases, accuracy }) ));
+//! assert_eq!(rule, Ok( (1, Rule { cases, accuracy }) ));
 //! ```
 //!
 //! # References
@@ -103,8 +103,109 @@ pub struct Rule<A, C> {
 #[derive(Debug, PartialEq, PartialOrd)]
 pub struct Accuracy(pub f64);
 
+/// An error encountered while inducing or evaluating a rule.
+///
+/// Prior to this, misuse (empty input, mismatched lengths) either surfaced as a confusing `None`
+/// or risked a later panic; these variants let a caller tell those cases apart from "no usable
+/// attribute".
+#[derive(Debug, PartialEq, Eq)]
+pub enum InductionError {
+    /// There were no rows to learn from.
+    EmptyDataset,
+    /// `attributes` and `classes` did not have the same number of rows.
+    MismatchedLengths { attribute_rows: usize, class_rows: usize },
+    /// `attributes` had no columns to generate a rule from.
+    NoAttributes,
+    /// Every attribute was rejected (e.g. by `InductionParams::distinct_above`), so no usable
+    /// rule could be found.
+    AllAttributesRejected,
+}
+
+impl std::fmt::Display for InductionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            InductionError::EmptyDataset => write!(f, "no rows to learn from"),
+            InductionError::MismatchedLengths { attribute_rows, class_rows } => write!(
+                f,
+                "attributes has {} row(s) but classes has {} - they must match",
+                attribute_rows, class_rows
+            ),
+            InductionError::NoAttributes => write!(f, "attributes has no columns"),
+            InductionError::AllAttributesRejected => {
+                write!(f, "every attribute was rejected, so no usable rule was found")
+            }
+        }
+    }
+}
+
+impl std::error::Error for InductionError {}
+
+/// A `Case`-like prediction that carries the full class distribution observed for an attribute
+/// value, rather than collapsing it to a single predicted class.
+#[derive(Debug, PartialEq)]
+pub struct ProbabilisticCase<A, C> {
+    /// The attribute value this case matches against.
+    pub attribute_value: A,
+    /// The normalized frequency of each class observed for this attribute value during
+    /// induction, as `(class, probability)` pairs.
+    pub class_probs: Vec<(C, f64)>,
+}
+
+/// A single rule produced by the PRISM covering algorithm: a conjunction of attribute=value
+/// tests (the antecedent), and the class predicted once every test is satisfied.
+///
+/// Unlike a 1R `Case`, which tests a single attribute, a `ConjunctiveRule`'s antecedent may test
+/// several columns at once.
+#[derive(Debug, PartialEq)]
+pub struct ConjunctiveRule<A, C> {
+    /// The `(column, value)` tests that must all hold for this rule to fire, in the order they
+    /// were added.
+    pub antecedent: Vec<(usize, A)>,
+    /// The class predicted once every test in the antecedent is satisfied.
+    pub predicted_class: C,
+}
+
+/// Precision, recall, and F1 score for a single class within a `ClassificationReport`.
+#[derive(Debug, PartialEq)]
+pub struct ClassMetrics {
+    /// Of the rows predicted as this class, the fraction that actually were.
+    pub precision: f64,
+    /// Of the rows that actually were this class, the fraction predicted as such.
+    pub recall: f64,
+    /// The harmonic mean of `precision` and `recall`.
+    pub f1: f64,
+}
+
+/// A detailed evaluation of a rule against a data set: a confusion matrix and per-class
+/// precision/recall/F1, alongside the plain `Accuracy` that `evaluate` already provides.
+///
+/// See `evaluate_detailed`.
+#[derive(Debug, PartialEq)]
+pub struct ClassificationReport<C> {
+    /// The overall accuracy, as returned by `evaluate`.
+    pub accuracy: Accuracy,
+    /// The distinct classes, in the order used to index `confusion_matrix` and `per_class`.
+    pub classes: Vec<C>,
+    /// `confusion_matrix[i][j]` is the number of rows predicted as `classes[i]` whose true class
+    /// was `classes[j]`.
+    pub confusion_matrix: Vec<Vec<usize>>,
+    /// Precision, recall, and F1 for each class, in the same order as `classes`.
+    pub per_class: Vec<ClassMetrics>,
+    /// The number of rows whose attribute value matched no case, so no prediction could be made.
+    pub unmatched: usize,
+}
+
 mod induction;
-pub use induction::discover;
+pub use induction::{
+    discover, discover_prism, discover_proba_for_attribute, discover_with, discover_zero_r,
+    InductionParams,
+};
 
 mod evaluation;
-pub use evaluation::{evaluate, interpret};
+pub use evaluation::{
+    evaluate, evaluate_detailed, evaluate_with_default, interpret, interpret_or_default,
+    interpret_proba,
+};
+
+mod validation;
+pub use validation::{cross_validate, train_test_split, CrossValidationResult, Repeat};
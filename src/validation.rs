@@ -0,0 +1,158 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at https://mozilla.org/MPL/2.0/.
+
+use super::evaluation::evaluate;
+use super::induction::discover;
+use super::{Accuracy, Rule};
+use ndarray::{Array1, Array2, ArrayView, Ix1, Ix2};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::hash::Hash;
+
+/// Split a data set into a training and a test set, using a seeded RNG so the split is
+/// reproducible.
+///
+/// # Arguments
+///
+/// * `attributes` - rows containing attribute values as columns.
+/// * `classes` - the true classification for each row.
+/// * `fraction` - the fraction of rows (rounded to the nearest whole row) to put in the training
+///   set; the remainder go to the test set.
+/// * `seed` - seeds the RNG used to shuffle rows before splitting, so the same seed always
+///   produces the same split.
+///
+/// # Result
+///
+/// A tuple of `(train_attributes, train_classes, test_attributes, test_classes)`.
+///
+pub fn train_test_split<A, C>(
+    attributes: &ArrayView<A, Ix2>,
+    classes: &ArrayView<C, Ix1>,
+    fraction: f64,
+    seed: u64,
+) -> (Array2<A>, Array1<C>, Array2<A>, Array1<C>)
+where
+    A: Clone,
+    C: Clone,
+{
+    let nrows = attributes.nrows();
+
+    let mut indices: Vec<usize> = (0..nrows).collect();
+    let mut rng = StdRng::seed_from_u64(seed);
+    indices.shuffle(&mut rng);
+
+    let split_at = (nrows as f64 * fraction).round() as usize;
+    let (train_indices, test_indices) = indices.split_at(split_at);
+
+    let train_attributes = select_rows(attributes, train_indices);
+    let train_classes = select_values(classes, train_indices);
+    let test_attributes = select_rows(attributes, test_indices);
+    let test_classes = select_values(classes, test_indices);
+
+    (train_attributes, train_classes, test_attributes, test_classes)
+}
+
+/// Gather the given row indices out of a 2D array, cloning each value.
+///
+/// Equivalent to `ArrayBase::select`, but without that method's `Copy` bound - this crate's
+/// attribute/class types (e.g. `String`) are only expected to be `Clone`.
+fn select_rows<T: Clone>(array: &ArrayView<T, Ix2>, indices: &[usize]) -> Array2<T> {
+    let ncols = array.ncols();
+    let data: Vec<T> =
+        indices.iter().flat_map(|&i| (0..ncols).map(move |j| array[[i, j]].clone())).collect();
+
+    Array2::from_shape_vec((indices.len(), ncols), data)
+        .expect("indices.len() rows of ncols columns is always a valid shape")
+}
+
+/// Gather the given indices out of a 1D array, cloning each value.
+fn select_values<T: Clone>(array: &ArrayView<T, Ix1>, indices: &[usize]) -> Array1<T> {
+    Array1::from(indices.iter().map(|&i| array[i].clone()).collect::<Vec<T>>())
+}
+
+/// The outcome of a single train/test repeat within `cross_validate`.
+#[derive(Debug)]
+pub struct Repeat<A, C> {
+    /// The rule discovered on this repeat's training split, and the column it applies to.
+    /// `None` if `discover` couldn't find a usable rule (see `InductionError`).
+    pub rule: Option<(usize, Rule<A, C>)>,
+    /// The accuracy of `rule` on this repeat's held-out test split, or `None` if the test split
+    /// was empty and accuracy couldn't be measured (see `InductionError::EmptyDataset`).
+    pub test_accuracy: Option<Accuracy>,
+}
+
+/// The outcome of repeated train/test evaluation, as performed by `cross_validate`.
+#[derive(Debug)]
+pub struct CrossValidationResult<A, C> {
+    /// The mean test accuracy across the repeats whose accuracy could be measured (repeats with
+    /// an empty test split, i.e. `test_accuracy: None`, are excluded rather than counted as 0).
+    /// `None` if no repeat yielded a measurable accuracy (e.g. `fraction = 1.0`, or `repeats = 0`).
+    pub mean_accuracy: Option<f64>,
+    /// The population standard deviation of `test_accuracy`, over the same measured repeats as
+    /// `mean_accuracy`. `None` under the same conditions as `mean_accuracy`.
+    pub stddev_accuracy: Option<f64>,
+    /// One entry per repeat, in order.
+    pub repeats: Vec<Repeat<A, C>>,
+}
+
+/// Repeatedly split the data into a training and test set, `discover` a rule on the training
+/// set, and `evaluate` it on the held-out test set, to get an honest estimate of how well the
+/// rule generalizes (rather than just its training-set accuracy).
+///
+/// # Arguments
+///
+/// * `attributes` - rows containing attribute values as columns.
+/// * `classes` - the true classification for each row.
+/// * `fraction` - the fraction of rows to use for training on each repeat (see
+///   `train_test_split`).
+/// * `repeats` - how many times to repeat the train/test split and evaluation.
+/// * `seed` - seeds the RNG; repeat `i` uses `seed.wrapping_add(i as u64)`, so the whole run is
+///   reproducible.
+///
+pub fn cross_validate<A, C>(
+    attributes: &ArrayView<A, Ix2>,
+    classes: &ArrayView<C, Ix1>,
+    fraction: f64,
+    repeats: usize,
+    seed: u64,
+) -> CrossValidationResult<A, C>
+where
+    A: Eq + Hash + Clone + std::fmt::Debug,
+    C: Eq + Hash + Clone + std::fmt::Debug,
+{
+    let results: Vec<Repeat<A, C>> = (0..repeats)
+        .map(|i| {
+            let repeat_seed = seed.wrapping_add(i as u64);
+            let (train_attributes, train_classes, test_attributes, test_classes) =
+                train_test_split(attributes, classes, fraction, repeat_seed);
+
+            let rule = discover(&train_attributes.view(), &train_classes.view()).ok();
+
+            let test_accuracy = match &rule {
+                Some((col, rule)) => {
+                    evaluate(&rule.cases, &test_attributes.column(*col), &test_classes.view()).ok()
+                }
+                None if test_classes.is_empty() => None,
+                None => Some(Accuracy(0.0)),
+            };
+
+            Repeat { rule, test_accuracy }
+        })
+        .collect();
+
+    let accuracies: Vec<f64> =
+        results.iter().filter_map(|repeat| repeat.test_accuracy.as_ref().map(|a| a.0)).collect();
+
+    let (mean_accuracy, stddev_accuracy) = if accuracies.is_empty() {
+        (None, None)
+    } else {
+        let mean = accuracies.iter().sum::<f64>() / accuracies.len() as f64;
+        let variance = accuracies.iter().map(|a| (a - mean).powi(2)).sum::<f64>()
+            / accuracies.len() as f64;
+        (Some(mean), Some(variance.sqrt()))
+    };
+
+    CrossValidationResult { mean_accuracy, stddev_accuracy, repeats: results }
+}